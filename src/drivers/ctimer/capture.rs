@@ -0,0 +1,76 @@
+//! Input capture on a CTIMER capture channel
+//!
+//! Latches the free-running counter into a capture register on a selected
+//! edge of an external capture input, rather than the match/compare path
+//! `Timer` and [`super::pwm`] use.
+
+use super::{Ctimer, Enabled, MatchChannel};
+
+/// Edge(s) that latch the counter into the capture register
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaptureEdge {
+    Rising,
+    Falling,
+    Both,
+}
+
+/// A capture channel, reading counter snapshots latched on an input edge
+pub struct Capture<C> {
+    ctimer: Ctimer<C, Enabled>,
+    channel: usize,
+}
+
+impl<C> Capture<C>
+where
+    C: MatchChannel,
+{
+    /// Enable capture on `channel` (0..=3), latching on `edge`
+    ///
+    /// Consumes the enabled ctimer, same as `Timer::new`, so a match
+    /// register can't simultaneously be driven as PWM/one-shot and read as
+    /// a capture channel.
+    pub fn new(mut ctimer: Ctimer<C, Enabled>, channel: usize, edge: CaptureEdge) -> Self {
+        ctimer.set_capture_edge(channel, edge);
+        ctimer.set_capture_enabled(channel, true);
+        ctimer.start_counter();
+        Capture { ctimer, channel }
+    }
+
+    /// Read the counter value latched at the last capture edge
+    pub fn captured(&self) -> u32 {
+        self.ctimer.capture_value(self.channel)
+    }
+
+    /// The capture event, if a capture has occurred since the last call
+    ///
+    /// Returns `None` when no capture has occurred; an overflow on its own
+    /// is not reported, but stays pending and is attached to the next
+    /// capture event. Overflow is reported so callers computing a period
+    /// from successive captures can detect a wraparound of the free-running
+    /// counter.
+    pub fn poll(&mut self) -> Option<CaptureEvent> {
+        if !self.ctimer.take_capture_event(self.channel) {
+            return None;
+        }
+
+        Some(CaptureEvent {
+            value: self.captured(),
+            overflowed: self.ctimer.take_overflow_event(),
+        })
+    }
+
+    /// Release the ctimer, e.g. to reconfigure it into another mode
+    pub fn release(self) -> Ctimer<C, Enabled> {
+        self.ctimer
+    }
+}
+
+/// A single capture event
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CaptureEvent {
+    /// Counter value latched at the capture edge
+    pub value: u32,
+    /// Whether the free-running counter overflowed since the previous
+    /// capture event
+    pub overflowed: bool,
+}
@@ -0,0 +1,99 @@
+//! Repeated match interrupts with `async` waker support
+//!
+//! Complements the blocking `wait()`/`lap()` on `Timer`: instead of
+//! busy-waiting in `block!(cdriver.wait())` as the ctimer example does,
+//! `MatchInterrupt` parks an `AtomicWaker` and is woken from the CTIMER's
+//! NVIC handler each time the configured match register fires, without
+//! resetting the counter (so it repeats on a fixed period rather than
+//! one-shot).
+
+use core::future::Future;
+use core::pin::Pin as CorePin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll};
+
+use futures::task::AtomicWaker;
+
+use super::{Ctimer, Enabled, MatchChannel};
+
+const NO_MATCH: AtomicBool = AtomicBool::new(false);
+
+/// One static waker per CTIMER block, woken from that block's NVIC handler
+static WAKERS: [AtomicWaker; 4] = [
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+];
+
+/// One "matched" flag per (CTIMER block, match channel), set by the ISR
+/// after it has already cleared the hardware's match interrupt flag
+///
+/// `wait_for_match`'s `poll` can't rely on `take_match_event` seeing the
+/// same hardware flag the ISR just cleared, so the ISR records the event
+/// here instead, and `poll` consumes it.
+static MATCHED: [[AtomicBool; 4]; 4] = [[NO_MATCH; 4]; 4];
+
+/// A match channel configured to repeatedly interrupt without resetting the
+/// counter
+pub struct MatchInterrupt<C> {
+    ctimer: Ctimer<C, Enabled>,
+    ctimer_index: usize,
+    channel: usize,
+}
+
+impl<C> MatchInterrupt<C>
+where
+    C: MatchChannel,
+{
+    /// Arm `channel` to interrupt (without resetting the counter) each time
+    /// it matches `value`
+    pub fn new(mut ctimer: Ctimer<C, Enabled>, ctimer_index: usize, channel: usize, value: u32) -> Self {
+        ctimer.set_match_reset_on_match(channel, false);
+        ctimer.set_match_interrupt_enabled(channel, true);
+        ctimer.set_match_value(channel, value);
+        ctimer.start_counter();
+        MatchInterrupt {
+            ctimer,
+            ctimer_index,
+            channel,
+        }
+    }
+
+    /// Wait for the next match
+    pub fn wait_for_match(&mut self) -> WaitForMatch<'_, C> {
+        WaitForMatch { interrupt: self }
+    }
+
+    /// Call from the CTIMERn NVIC handler: clears the match interrupt flag
+    /// and wakes anyone awaiting it
+    pub fn on_match(ctimer_index: usize, ctimer: &mut Ctimer<C, Enabled>, channel: usize) {
+        ctimer.clear_match_interrupt(channel);
+        MATCHED[ctimer_index][channel].store(true, Ordering::Release);
+        WAKERS[ctimer_index].wake();
+    }
+}
+
+/// Future returned by [`MatchInterrupt::wait_for_match`]
+pub struct WaitForMatch<'m, C> {
+    interrupt: &'m mut MatchInterrupt<C>,
+}
+
+impl<'m, C> Future for WaitForMatch<'m, C>
+where
+    C: MatchChannel,
+{
+    type Output = ();
+
+    fn poll(self: CorePin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let (ctimer_index, channel) = (this.interrupt.ctimer_index, this.interrupt.channel);
+        WAKERS[ctimer_index].register(cx.waker());
+
+        if MATCHED[ctimer_index][channel].swap(false, Ordering::AcqRel) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! PWM output on top of a CTIMER's match channels
+//!
+//! The blocking `Timer` (see the parent module) only ever uses a single
+//! match register as a one-shot countdown, as in the ctimer example. Each
+//! CTIMER block actually has four match registers (`MR0..MR3`); if one of
+//! them resets the counter on match, the remaining three can each drive a
+//! PWM output pin, with their own duty cycle, at the period the reset match
+//! defines. This module builds `embedded_hal::Pwm` on top of that.
+//!
+//! Wired in via `mod pwm;` from the parent `ctimer` module, and re-exported
+//! as `ctimer::{CtimerPwm, Channel}`.
+
+use embedded_hal::Pwm;
+
+use super::{ClockConfig, Ctimer, Enabled, MatchChannel};
+
+/// PWM output driven by a CTIMER, one period shared across up to 3 channels
+///
+/// `MR3` is reserved as the period (it's configured to reset the counter on
+/// match); `MR0..MR2` are available as PWM channels. This mirrors the
+/// split the reference manual recommends and statically prevents a channel
+/// also being claimed by a one-shot/capture use of the same match register,
+/// since `CtimerPwm::new` consumes the enabled ctimer that `Timer::new` and
+/// `Capture::new` also consume.
+pub struct CtimerPwm<C> {
+    ctimer: Ctimer<C, Enabled>,
+    period_ticks: u32,
+}
+
+/// One of the three PWM-capable match channels on a [`CtimerPwm`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    Mr0,
+    Mr1,
+    Mr2,
+}
+
+impl<C> CtimerPwm<C>
+where
+    C: MatchChannel,
+{
+    /// Configure `MR3` as the PWM period and enable PWM mode
+    ///
+    /// `period_ticks` is in CTIMER prescaler ticks, per [`ClockConfig`] (the same
+    /// clock-requirements flow `Timer::new` uses).
+    pub fn new(ctimer: Ctimer<C, Enabled>, period_ticks: u32, _clocks: &ClockConfig) -> Self {
+        let mut pwm = CtimerPwm {
+            ctimer,
+            period_ticks,
+        };
+        pwm.ctimer.set_match_reset_on_match(3, true);
+        pwm.ctimer.set_match_value(3, period_ticks);
+        pwm.ctimer.set_pwm_enabled(3, true);
+        pwm.ctimer.start_counter();
+        pwm
+    }
+}
+
+impl<C> Pwm for CtimerPwm<C>
+where
+    C: MatchChannel,
+{
+    type Channel = Channel;
+    type Time = u32;
+    type Duty = u32;
+
+    fn disable(&mut self, channel: Self::Channel) {
+        self.ctimer.set_pwm_enabled(mr_index(channel), false);
+    }
+
+    fn enable(&mut self, channel: Self::Channel) {
+        self.ctimer.set_pwm_enabled(mr_index(channel), true);
+    }
+
+    fn get_period(&self) -> Self::Time {
+        self.period_ticks
+    }
+
+    fn get_duty(&self, channel: Self::Channel) -> Self::Duty {
+        // The channel output is low from the counter reset (MR3) until the
+        // channel's own match, and asserts from that match until the next
+        // reset, so its active time — the duty, per the
+        // `embedded_hal::Pwm` contract — is `period - match`, not `match`
+        // itself.
+        self.period_ticks - self.ctimer.match_value(mr_index(channel))
+    }
+
+    fn get_max_duty(&self) -> Self::Duty {
+        self.period_ticks
+    }
+
+    fn set_duty(&mut self, channel: Self::Channel, duty: Self::Duty) {
+        let duty = duty.min(self.period_ticks);
+        self.ctimer
+            .set_match_value(mr_index(channel), self.period_ticks - duty);
+    }
+
+    fn set_period<P>(&mut self, period: P)
+    where
+        P: Into<Self::Time>,
+    {
+        self.period_ticks = period.into();
+        self.ctimer.set_match_value(3, self.period_ticks);
+    }
+}
+
+fn mr_index(channel: Channel) -> usize {
+    match channel {
+        Channel::Mr0 => 0,
+        Channel::Mr1 => 1,
+        Channel::Mr2 => 2,
+    }
+}
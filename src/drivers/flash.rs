@@ -0,0 +1,200 @@
+//! On-chip flash storage
+//!
+//! Wraps the LPC55 flash controller (`FLASH`) so higher layers can persist
+//! data without hand-rolling the controller's command sequence. Exposes the
+//! `embedded-storage` [`ReadStorage`]/[`Storage`]/[`ReadNorFlash`]/[`NorFlash`]
+//! traits, so e.g. a key-value store crate can be built directly on top of
+//! [`Flash`].
+//!
+//! Erases operate on whole pages and programs on aligned flash words,
+//! because that's the granularity the controller itself enforces; rather
+//! than silently rounding or corrupting neighbouring data, out-of-alignment
+//! requests are rejected with [`Error::NotAligned`].
+//!
+//! Wired in via `pub mod flash;` from the parent `drivers` module (not part
+//! of this diff), re-exported as `drivers::Flash`.
+
+use embedded_storage::nor_flash::{ErrorType, NorFlash, NorFlashError, NorFlashErrorKind, ReadNorFlash};
+use embedded_storage::{ReadStorage, Storage};
+
+use crate::typestates::init_state;
+
+/// Size in bytes of one flash page (the erase granularity)
+pub const PAGE_SIZE: usize = 512;
+/// Size in bytes of one flash word (the program granularity)
+pub const WORD_SIZE: usize = 16;
+/// Base address of the memory-mapped flash region
+pub const FLASH_BASE: usize = 0x0000_0000;
+/// Size in bytes of the on-chip flash
+pub const FLASH_SIZE: usize = 630 * 1024;
+
+/// Driver for the on-chip flash controller
+///
+/// Built from `hal.flash.enabled(&mut hal.syscon)`, following the same
+/// `enabled`/`release` convention as the other peripheral drivers.
+pub struct Flash {
+    flash: crate::peripherals::flash::Flash<init_state::Enabled>,
+}
+
+/// Errors surfaced by [`Flash`] operations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The requested offset/length wasn't aligned to the operation's
+    /// granularity ([`PAGE_SIZE`] for erase, [`WORD_SIZE`] for program)
+    NotAligned,
+    /// The requested range fell outside the flash region, or `offset +
+    /// length` overflowed
+    OutOfBounds,
+    /// The controller reported an ECC or read error for the operation
+    ReadError,
+    /// The controller reported that a program or erase command failed
+    /// (`INT_STATUS` `FAIL`/`ERR` bits set after the command completed)
+    CommandFailed,
+}
+
+impl NorFlashError for Error {
+    fn kind(&self) -> NorFlashErrorKind {
+        match self {
+            Error::NotAligned => NorFlashErrorKind::NotAligned,
+            Error::OutOfBounds => NorFlashErrorKind::OutOfBounds,
+            Error::ReadError | Error::CommandFailed => NorFlashErrorKind::Other,
+        }
+    }
+}
+
+impl ErrorType for Flash {
+    type Error = Error;
+}
+
+impl Flash {
+    /// Take ownership of the enabled flash controller
+    pub fn new(flash: crate::peripherals::flash::Flash<init_state::Enabled>) -> Self {
+        Flash { flash }
+    }
+
+    /// Release the underlying peripheral
+    pub fn release(self) -> crate::peripherals::flash::Flash<init_state::Enabled> {
+        self.flash
+    }
+
+    fn wait_for_command(&mut self) -> Result<(), Error> {
+        // Erase and program commands end the same way: poll
+        // `INT_STATUS.DONE`, then check `FAIL`/`ERR`, mirroring the
+        // wait-for-flush pattern other MCU flash drivers use. The
+        // set-read-mode command that follows is only polled for `DONE`.
+        while !self.flash.command_done() {}
+
+        if self.flash.command_failed() {
+            return Err(Error::CommandFailed);
+        }
+
+        // Re-arm the controller's read path after a write/erase command, so
+        // subsequent memory-mapped reads see the new contents rather than a
+        // stale prefetch.
+        self.flash.set_read_mode();
+        while !self.flash.command_done() {}
+
+        Ok(())
+    }
+
+    fn erase_page(&mut self, page: usize) -> Result<(), Error> {
+        self.flash.start_erase_page(page);
+        self.wait_for_command()
+    }
+
+    fn program_word(&mut self, word: usize, data: &[u8; WORD_SIZE]) -> Result<(), Error> {
+        self.flash.load_program_buffer(data);
+        self.flash.start_program_word(word);
+        self.wait_for_command()
+    }
+}
+
+impl ReadNorFlash for Flash {
+    const READ_SIZE: usize = 1;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        let offset = offset as usize;
+        let end = offset.checked_add(bytes.len()).ok_or(Error::OutOfBounds)?;
+        if end > FLASH_SIZE {
+            return Err(Error::OutOfBounds);
+        }
+
+        // Reads go through the normal memory-mapped address space; the ECC
+        // checker lives behind that same access, so a failing read here
+        // would fault rather than return data, which is why `read_checked`
+        // is used instead of a raw slice copy.
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = self
+                .flash
+                .read_checked(FLASH_BASE + offset + i)
+                .map_err(|_| Error::ReadError)?;
+        }
+
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        FLASH_SIZE
+    }
+}
+
+impl ReadStorage for Flash {
+    type Error = Error;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        ReadNorFlash::read(self, offset, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        FLASH_SIZE
+    }
+}
+
+impl NorFlash for Flash {
+    const WRITE_SIZE: usize = WORD_SIZE;
+    const ERASE_SIZE: usize = PAGE_SIZE;
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+        let (from, to) = (from as usize, to as usize);
+        if from > to {
+            return Err(Error::OutOfBounds);
+        }
+        if from % PAGE_SIZE != 0 || to % PAGE_SIZE != 0 {
+            return Err(Error::NotAligned);
+        }
+        if to > FLASH_SIZE {
+            return Err(Error::OutOfBounds);
+        }
+
+        for page in (from..to).step_by(PAGE_SIZE) {
+            self.erase_page(page / PAGE_SIZE)?;
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        let offset = offset as usize;
+        if offset % WORD_SIZE != 0 || bytes.len() % WORD_SIZE != 0 {
+            return Err(Error::NotAligned);
+        }
+        let end = offset.checked_add(bytes.len()).ok_or(Error::OutOfBounds)?;
+        if end > FLASH_SIZE {
+            return Err(Error::OutOfBounds);
+        }
+
+        for (i, chunk) in bytes.chunks(WORD_SIZE).enumerate() {
+            let mut word = [0u8; WORD_SIZE];
+            word.copy_from_slice(chunk);
+            self.program_word(offset / WORD_SIZE + i, &word)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Storage for Flash {
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        NorFlash::write(self, offset, bytes)
+    }
+}
@@ -0,0 +1,160 @@
+//! Low-power mode control via PMC
+//!
+//! `ClockRequirements`/`pmc` (see the `clocks` module) only ever bring the
+//! chip up into a running clock tree; there was previously no supported way
+//! back down into sleep, deep-sleep or power-down. This module sequences
+//! the PMC power-control registers for each of those modes, and lets a
+//! configured PINT channel (see `peripherals::pint`) be registered as a
+//! wakeup source, so `enter_deep_sleep`/`enter_power_down` can block until
+//! that edge fires and then resume cleanly.
+//!
+//! Wired in via `pub mod power;` from the parent `drivers` module (not part
+//! of this diff), re-exported as `drivers::Power`.
+
+use cortex_m::asm::wfi;
+
+use crate::peripherals::pint::Pint;
+use crate::peripherals::pmc::Pmc;
+use crate::typestates::init_state::Enabled;
+use crate::ClockRequirements;
+
+/// A source that can wake the chip from a low-power mode
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wakeup {
+    /// Wake on an edge from the given PINT channel (0..=7), as configured by
+    /// `Pint::enable_interrupt` or the pattern-match builder
+    Pint(usize),
+    /// Wake on the RTC alarm
+    Rtc,
+    /// Wake on activity on the USB bus
+    UsbWakeup,
+}
+
+/// A set of enabled wakeup sources, built up with [`Wakeups::with`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Wakeups {
+    mask: u32,
+}
+
+impl Wakeups {
+    /// Start with no wakeup sources enabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable `source` as a wakeup source
+    pub fn with(mut self, source: Wakeup) -> Self {
+        self.mask |= 1 << starten_bit(source);
+        self
+    }
+}
+
+fn starten_bit(source: Wakeup) -> u8 {
+    // Bit positions within PMC's `STARTERP0`/`STARTERP1`, per the PINT
+    // channels (0..=7), RTC and USB wakeup lines.
+    match source {
+        Wakeup::Pint(channel) => {
+            debug_assert!(channel < 8, "PINT only has 8 channels");
+            channel as u8
+        }
+        Wakeup::Rtc => 17,
+        Wakeup::UsbWakeup => 20,
+    }
+}
+
+/// Error returned when a low-power mode can't be entered as configured
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The requested mode needs at least one [`Wakeup`] source to ever
+    /// resume, but `wakeups` was empty
+    NoWakeupSource,
+    /// The currently configured clock tree is incompatible with the
+    /// requested mode (e.g. a PLL left running into deep-sleep, which the
+    /// PMC can't gate while it's selected as a clock source)
+    IncompatibleClockTree,
+}
+
+/// Driver for entering and leaving low-power modes
+///
+/// Built from `hal.pmc`, following the same ownership convention as the
+/// other drivers: it takes the peripheral by value and gives it back on
+/// [`Power::release`].
+pub struct Power {
+    pmc: Pmc<Enabled>,
+}
+
+impl Power {
+    /// Take ownership of the enabled PMC
+    pub fn new(pmc: Pmc<Enabled>) -> Self {
+        Power { pmc }
+    }
+
+    /// Release the underlying peripheral
+    pub fn release(self) -> Pmc<Enabled> {
+        self.pmc
+    }
+
+    /// Enter sleep mode: gate the core clock, wake on any enabled interrupt
+    ///
+    /// Returns once an interrupt wakes the core; clock state is unaffected,
+    /// since sleep doesn't gate the main clock.
+    pub fn enter_sleep(&mut self) {
+        self.pmc.set_deep_sleep(false);
+        wfi();
+    }
+
+    /// Enter deep-sleep: additionally gate the main clock, wake only on
+    /// `wakeups`
+    ///
+    /// Restores the clock tree on wake, since deep-sleep gates the main
+    /// clock and PMC doesn't do this automatically.
+    pub fn enter_deep_sleep(&mut self, wakeups: Wakeups, clocks: &ClockRequirements) -> Result<(), Error> {
+        if wakeups.mask == 0 {
+            return Err(Error::NoWakeupSource);
+        }
+        if !clocks.compatible_with_deep_sleep() {
+            return Err(Error::IncompatibleClockTree);
+        }
+
+        self.pmc.set_starten(wakeups.mask);
+        self.pmc.set_deep_sleep(true);
+        wfi();
+        self.pmc.restore_clocks_after_wake(clocks);
+
+        Ok(())
+    }
+
+    /// Enter power-down: gate most analog domains in addition to the main
+    /// clock, wake only on `wakeups`
+    ///
+    /// Slower to resume from than deep-sleep, since analog blocks (PLLs,
+    /// regulators) need to restart; the clock tree is restored the same way
+    /// as after deep-sleep.
+    pub fn enter_power_down(&mut self, wakeups: Wakeups, clocks: &ClockRequirements) -> Result<(), Error> {
+        if wakeups.mask == 0 {
+            return Err(Error::NoWakeupSource);
+        }
+        if !clocks.compatible_with_power_down() {
+            return Err(Error::IncompatibleClockTree);
+        }
+
+        self.pmc.set_starten(wakeups.mask);
+        self.pmc.set_power_down_regulators(true);
+        self.pmc.set_deep_sleep(true);
+        wfi();
+        self.pmc.set_power_down_regulators(false);
+        self.pmc.restore_clocks_after_wake(clocks);
+
+        Ok(())
+    }
+
+    /// Register a configured PINT channel as a wakeup source
+    ///
+    /// Convenience wrapper so a `Pint` channel from the external-interrupt
+    /// example, or the pattern-match builder, can be passed straight into
+    /// [`Wakeups`] without the caller needing to know the channel index PMC
+    /// expects.
+    pub fn wakeup_on_pint(_pint: &Pint<Enabled>, channel: usize, wakeups: Wakeups) -> Wakeups {
+        wakeups.with(Wakeup::Pint(channel))
+    }
+}
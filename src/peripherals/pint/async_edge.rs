@@ -0,0 +1,207 @@
+//! Interrupt-driven, `async`-friendly edge detection on PINT channels
+//!
+//! The plain `enable_interrupt` path (see the parent module) requires the
+//! caller to busy-poll `rise`/`fall`, as the external-interrupt example
+//! does. Each of PINT's 8 channels has its own NVIC line (`PIN_INT0` ..
+//! `PIN_INT7`), so instead we can mask/unmask that line and park an
+//! `AtomicWaker`, and let the ISR do the wake-up. This is what lets PINT be
+//! driven from an embassy-style executor instead of a `loop {}`.
+//!
+//! Wired in via `mod async_edge;` from the parent `pint` module, and
+//! re-exported as `pint::{Channel, on_edge}`.
+
+use core::future::Future;
+use core::pin::Pin as CorePin;
+use core::sync::atomic::{AtomicU8, Ordering};
+use core::task::{Context, Poll};
+
+use cortex_m::peripheral::NVIC;
+use futures::task::AtomicWaker;
+
+use super::Mode;
+use crate::raw::Interrupt;
+use crate::reg_proxy::Shared;
+
+const RISE_FIRED: u8 = 1 << 0;
+const FALL_FIRED: u8 = 1 << 1;
+
+/// One static waker per PINT channel, woken from that channel's NVIC handler
+static WAKERS: [AtomicWaker; 8] = [
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+    AtomicWaker::new(),
+];
+
+/// One "an edge fired" flag per PINT channel, set by the ISR after it has
+/// already cleared the hardware latch
+///
+/// The future can't just re-read `rise`/`fall` after being woken: by the
+/// time it's polled, the ISR (see [`on_edge`]) has already cleared that
+/// same latch bit, so the hardware state alone can't tell the future
+/// whether it was woken because of a real edge. This flag is the record of
+/// that edge, consumed (cleared) by whichever `poll` observes it.
+static FIRED: [AtomicU8; 8] = [
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+    AtomicU8::new(0),
+];
+
+fn nvic_interrupt(channel: usize) -> Interrupt {
+    match channel {
+        0 => Interrupt::PIN_INT0,
+        1 => Interrupt::PIN_INT1,
+        2 => Interrupt::PIN_INT2,
+        3 => Interrupt::PIN_INT3,
+        4 => Interrupt::PIN_INT4,
+        5 => Interrupt::PIN_INT5,
+        6 => Interrupt::PIN_INT6,
+        7 => Interrupt::PIN_INT7,
+        _ => unreachable!("PINT only has 8 channels"),
+    }
+}
+
+/// A single PINT channel, used to `await` an edge
+///
+/// Obtained from `Pint::channel(n)`, which hands out [`Shared`] handles to
+/// the rise/fall latch registers (see `reg_proxy`), so the ISR
+/// and a pending future can't both read-modify-write the detect registers:
+/// the ISR only ever `act`s on its own channel's bit through a [`Shared`]
+/// handle.
+pub struct Channel {
+    index: usize,
+    rise: Shared<super::RISE>,
+    fall: Shared<super::FALL>,
+}
+
+impl Channel {
+    pub(super) fn new(index: usize, rise: Shared<super::RISE>, fall: Shared<super::FALL>) -> Self {
+        Channel { index, rise, fall }
+    }
+
+    /// Wait for the next occurrence of `mode` on this channel
+    ///
+    /// Unmasks this channel's NVIC line on first poll; the corresponding ISR
+    /// (see [`on_edge`]) clears the latch and wakes this future.
+    pub fn wait_for_edge(&mut self, mode: Mode) -> WaitForEdge<'_> {
+        WaitForEdge {
+            channel: self,
+            mode,
+            armed: false,
+        }
+    }
+}
+
+/// Future returned by [`Channel::wait_for_edge`]
+pub struct WaitForEdge<'c> {
+    channel: &'c mut Channel,
+    mode: Mode,
+    armed: bool,
+}
+
+impl<'c> Future for WaitForEdge<'c> {
+    type Output = ();
+
+    fn poll(self: CorePin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let index = this.channel.index;
+
+        WAKERS[index].register(cx.waker());
+
+        let wanted = match this.mode {
+            Mode::RisingEdge => RISE_FIRED,
+            Mode::FallingEdge => FALL_FIRED,
+            Mode::BothEdges => RISE_FIRED | FALL_FIRED,
+        };
+
+        if !this.armed {
+            // The line is masked (see `Drop`) whenever nothing is awaiting
+            // this channel, but an edge can still have latched and set
+            // `FIRED` while it was masked, or a previous wait for a
+            // different mode can have left bits this wait doesn't care
+            // about. Either way, a flag from before this wait started isn't
+            // this wait's edge: discard it rather than resolving `Ready`
+            // immediately.
+            FIRED[index].fetch_and(!wanted, Ordering::AcqRel);
+            // Safe: unmasking our own channel's line is independent of any
+            // other owner's access to PINT.
+            unsafe { NVIC::unmask(nvic_interrupt(index)) };
+            this.armed = true;
+            return Poll::Pending;
+        }
+
+        // Consume (rather than merely read) the flags the ISR set, so a
+        // second, unrelated poll doesn't see a stale edge again.
+        let fired = FIRED[index].fetch_and(!wanted, Ordering::AcqRel) & wanted;
+
+        if fired != 0 {
+            WAKERS[index].take();
+            return Poll::Ready(());
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<'c> Drop for WaitForEdge<'c> {
+    fn drop(&mut self) {
+        if self.armed {
+            // Re-mask the line once nothing is awaiting it, so edges that
+            // occur between this wait ending and the next one starting
+            // don't accumulate in `FIRED` and get misattributed to that
+            // later, possibly differently-moded, wait.
+            NVIC::mask(nvic_interrupt(self.channel.index));
+        }
+    }
+}
+
+/// Body shared by every `PIN_INTn` interrupt handler
+///
+/// Call this from the `#[interrupt]` function for the channel (see the
+/// `pint_channel_interrupt!` macro for a generator that does so), passing
+/// that channel's `Shared` rise/fall handles. `RISE`/`FALL` are single
+/// registers shared across all 8 channels, one bit per channel, so `index`
+/// doubles as the bit within each register. Clears whichever latch fired,
+/// records it in [`FIRED`] so the future can observe it after the latch is
+/// gone, and wakes the channel's parked waker, if any.
+pub fn on_edge(index: usize, rise: &Shared<super::RISE>, fall: &Shared<super::FALL>) {
+    let mut fired = 0;
+
+    if rise.is_set(index as u8) {
+        rise.act(index as u8);
+        fired |= RISE_FIRED;
+    }
+    if fall.is_set(index as u8) {
+        fall.act(index as u8);
+        fired |= FALL_FIRED;
+    }
+
+    FIRED[index].fetch_or(fired, Ordering::AcqRel);
+    WAKERS[index].wake();
+}
+
+/// Generate a `#[interrupt] fn PIN_INTn()` for a given channel
+///
+/// Expands to the boilerplate ISR that forwards into [`on_edge`]. `$rise`
+/// and `$fall` must be expressions yielding that channel's `Shared<RISE>`/
+/// `Shared<FALL>` handles (typically obtained once and stashed in a
+/// `static` `Mutex<RefCell<Option<_>>>` at split time, the usual pattern for
+/// sharing peripheral state with an ISR).
+#[macro_export]
+macro_rules! pint_channel_interrupt {
+    ($channel:literal, $name:ident, $rise:expr, $fall:expr) => {
+        #[cortex_m_rt::interrupt]
+        fn $name() {
+            $crate::peripherals::pint::on_edge($channel, &$rise, &$fall);
+        }
+    };
+}
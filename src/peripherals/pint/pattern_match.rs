@@ -0,0 +1,212 @@
+//! Typed builder for PINT's boolean pattern-match engine
+//!
+//! `enable_interrupt` (see the parent module) only drives PINT's simple
+//! edge-detect path: one input, one mode, one latch bit. The same block of
+//! hardware also contains a pattern-match engine that ANDs together up to
+//! eight bit slices into product terms, then ORs the product terms into a
+//! single boolean result. This module wires that engine up behind a builder,
+//! so callers don't have to hand-assemble `PMCFG`/`PMSRC` themselves.
+//!
+//! Wired in via `mod pattern_match;` from the parent `pint` module, and
+//! re-exported as `pint::{PatternMatch, Slice}`.
+
+use super::{InputMux, Pint};
+use crate::peripherals::gpio::Pin;
+
+/// Maximum number of bit slices the pattern-match engine provides
+pub const SLICE_BUDGET: usize = 8;
+
+/// Detection mode for a single pattern-match bit slice
+///
+/// Mirrors the `PMCFG_CFGn` encoding: constant levels, sticky (latching)
+/// levels, and edges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliceMode {
+    /// Slice output is `1` for as long as the input reads high
+    ConstHigh,
+    /// Slice output is `1` for as long as the input reads low
+    ConstLow,
+    /// Slice output latches to `1` the first time the input reads high
+    StickyHigh,
+    /// Slice output latches to `1` the first time the input reads low
+    StickyLow,
+    /// Slice output pulses `1` for one cycle on a rising edge
+    RisingEdge,
+    /// Slice output pulses `1` for one cycle on a falling edge
+    FallingEdge,
+    /// Slice output pulses `1` for one cycle on either edge
+    AnyEdge,
+}
+
+impl SliceMode {
+    fn bits(self) -> u8 {
+        match self {
+            SliceMode::ConstHigh => 0b000,
+            SliceMode::ConstLow => 0b001,
+            SliceMode::StickyHigh => 0b010,
+            SliceMode::StickyLow => 0b011,
+            SliceMode::RisingEdge => 0b100,
+            SliceMode::FallingEdge => 0b101,
+            SliceMode::AnyEdge => 0b110,
+        }
+    }
+
+    fn is_edge(self) -> bool {
+        matches!(
+            self,
+            SliceMode::RisingEdge | SliceMode::FallingEdge | SliceMode::AnyEdge
+        )
+    }
+}
+
+/// One bit slice bound to an input, with its detection mode
+///
+/// Constructed with the `Slice::{high, low, sticky_high, sticky_low,
+/// rising, falling, any_edge}` helpers, e.g. `Slice::rising(&pin)`.
+pub struct Slice<'p> {
+    pin: &'p dyn Pin,
+    mode: SliceMode,
+}
+
+macro_rules! slice_ctor {
+    ($name:ident, $mode:expr) => {
+        /// Create a bit slice bound to `pin`, using this detection mode
+        pub fn $name(pin: &'p dyn Pin) -> Self {
+            Slice { pin, mode: $mode }
+        }
+    };
+}
+
+impl<'p> Slice<'p> {
+    slice_ctor!(high, SliceMode::ConstHigh);
+    slice_ctor!(low, SliceMode::ConstLow);
+    slice_ctor!(sticky_high, SliceMode::StickyHigh);
+    slice_ctor!(sticky_low, SliceMode::StickyLow);
+    slice_ctor!(rising, SliceMode::RisingEdge);
+    slice_ctor!(falling, SliceMode::FallingEdge);
+    slice_ctor!(any_edge, SliceMode::AnyEdge);
+}
+
+/// Error returned when a pattern-match configuration can't be programmed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// More bit slices were requested across all product terms than the
+    /// hardware provides (see [`SLICE_BUDGET`])
+    TooManySlices,
+    /// `enable()` was called while the engine was already enabled; call
+    /// `disable()` first. Reprogramming `PMCFG` while `PMCTRL.PMATCH_SEL` is
+    /// set has side effects on the currently running match, so the builder
+    /// refuses to do it implicitly.
+    AlreadyEnabled,
+}
+
+/// Builder for the pattern-match engine
+///
+/// Accumulates product terms (a product term is the logical AND of
+/// consecutive slices, closed by the last slice's "endpoint" bit), then
+/// programs `PMSRC`/`PMCFG` and switches PINT into pattern-match mode on
+/// [`enable`](PatternMatch::enable).
+pub struct PatternMatch {
+    sources: [u8; SLICE_BUDGET],
+    modes: [SliceMode; SLICE_BUDGET],
+    endpoints: [bool; SLICE_BUDGET],
+    len: usize,
+}
+
+impl Default for PatternMatch {
+    fn default() -> Self {
+        PatternMatch {
+            sources: [0; SLICE_BUDGET],
+            modes: [SliceMode::ConstLow; SLICE_BUDGET],
+            endpoints: [false; SLICE_BUDGET],
+            len: 0,
+        }
+    }
+}
+
+impl PatternMatch {
+    /// Start building a pattern-match configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a product term: the logical AND of the given slices
+    ///
+    /// Slices are consumed from the shared budget of [`SLICE_BUDGET`] in the
+    /// order product terms are added. The overall match result is the
+    /// logical OR of every product term added this way.
+    pub fn product_term(mut self, mux: &mut InputMux, slices: &[Slice<'_>]) -> Result<Self, Error> {
+        if self.len + slices.len() > SLICE_BUDGET {
+            return Err(Error::TooManySlices);
+        }
+
+        for (i, slice) in slices.iter().enumerate() {
+            let idx = self.len + i;
+            self.sources[idx] = mux.pintsel(idx, slice.pin);
+            self.modes[idx] = slice.mode;
+            self.endpoints[idx] = i + 1 == slices.len();
+        }
+        self.len += slices.len();
+
+        Ok(self)
+    }
+
+    /// Program the engine and switch PINT into pattern-match mode
+    ///
+    /// Configuration (`PMSRC`/`PMCFG`) is written before pattern-match mode
+    /// is turned on, per the hardware's requirement that `PMCFG` not be
+    /// touched while matching is active.
+    pub fn enable(self, pint: &mut Pint<crate::peripherals::pint::Enabled>) -> Result<Matched, Error> {
+        if pint.pattern_match_enabled() {
+            return Err(Error::AlreadyEnabled);
+        }
+
+        for i in 0..self.len {
+            pint.set_pmsrc(i, self.sources[i]);
+            pint.set_pmcfg(i, self.modes[i].bits(), self.endpoints[i]);
+        }
+        // Every `enable()` call rewrites the full 8 slices, rather than
+        // trusting whatever `PMCFG` happened to be left by a previous
+        // configuration (or the chip's own reset state): a slice only
+        // contributes to the sum-of-products once it closes a product term
+        // (its endpoint bit is set), so leaving every unused slice's
+        // endpoint bit clear keeps it from forming a spurious term of its
+        // own, whatever value its (possibly stale) bound input happens to
+        // read.
+        for i in self.len..SLICE_BUDGET {
+            pint.set_pmsrc(i, 0);
+            pint.set_pmcfg(i, SliceMode::ConstLow.bits(), false);
+        }
+
+        pint.set_pattern_match_enabled(true);
+
+        Ok(Matched {
+            has_edge_term: self.modes[..self.len].iter().any(|m| m.is_edge()),
+        })
+    }
+}
+
+/// A programmed, running pattern-match configuration
+pub struct Matched {
+    has_edge_term: bool,
+}
+
+impl Matched {
+    /// Read the combined (sum-of-products) match result
+    ///
+    /// Edge-triggered product terms self-clear once read, matching the
+    /// hardware's pulsed detect behavior; level-based terms read back as
+    /// long as their condition holds.
+    pub fn is_matched(&self, pint: &Pint<crate::peripherals::pint::Enabled>) -> bool {
+        let matched = pint.pattern_match_detected();
+        if matched && self.has_edge_term {
+            pint.clear_pattern_match_detected();
+        }
+        matched
+    }
+
+    /// Disable the pattern-match engine, allowing reconfiguration
+    pub fn disable(self, pint: &mut Pint<crate::peripherals::pint::Enabled>) {
+        pint.set_pattern_match_enabled(false);
+    }
+}
@@ -10,7 +10,8 @@
 
 use core::marker::PhantomData;
 use core::mem::transmute;
-use core::ops::Deref;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicU32, Ordering};
 
 /// A proxy object for a register
 ///
@@ -98,6 +99,126 @@ pub unsafe trait Reg {
     fn get() -> *const Self::Target;
 }
 
+/// A uniquely owned handle to a register
+///
+/// Unlike `RegProxy`, which can be created as many times as the caller likes,
+/// `RegOwned` can only be obtained by splitting the peripheral that contains
+/// it, via [`reg_split!`](crate::reg_split). Since the split consumes the
+/// peripheral by value, only one `RegOwned<T>` for a given register can ever
+/// exist, so `modify`/`write` through it can never race with another part of
+/// the program. `RegOwned` is deliberately `!Copy`, `!Clone` and not `Send`,
+/// so the uniqueness guarantee also holds across threads: move it to the
+/// component that needs it instead.
+pub struct RegOwned<T>
+where
+    T: Reg,
+{
+    _marker: PhantomData<*mut T>,
+}
+
+impl<T> RegOwned<T>
+where
+    T: Reg,
+{
+    /// Create an owned handle to the register
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that no other `RegProxy<T>` or `RegOwned<T>`
+    /// for the same register is created for as long as this handle exists.
+    /// This is meant to be called exactly once per register, from within
+    /// [`reg_split!`](crate::reg_split).
+    pub unsafe fn new() -> Self {
+        RegOwned {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for RegOwned<T>
+where
+    T: Reg,
+{
+    type Target = T::Target;
+
+    fn deref(&self) -> &Self::Target {
+        // Safe for the same reasons as `RegProxy::deref`, see above.
+        unsafe { transmute(T::get()) }
+    }
+}
+
+impl<T> DerefMut for RegOwned<T>
+where
+    T: Reg,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Safe for the same reasons as `RegProxy::deref`. Exclusive access is
+        // additionally guaranteed by `RegOwned` only being constructible
+        // through `reg_split!`, which hands out at most one instance per
+        // register.
+        unsafe { transmute(T::get() as *mut Self::Target) }
+    }
+}
+
+/// A shared handle to a write-1-to-act register
+///
+/// Some registers, such as the PINT `rise`/`fall` latches, are safe to share
+/// between components as long as access is restricted to setting or clearing
+/// individual bits: writing a `1` acts on (and in these registers, clears)
+/// just that bit, and writing `0` is a no-op, so two components touching
+/// different bits can never race with each other. `Shared<T>` wraps such a
+/// register and only exposes an atomic single-bit write ([`act`](Shared::act))
+/// and a single-bit read ([`is_set`](Shared::is_set)), rather than the
+/// read-modify-write `modify` that `RegProxy` allows.
+pub struct Shared<T>
+where
+    T: Reg,
+{
+    _marker: PhantomData<*const T>,
+}
+
+impl<T> Shared<T>
+where
+    T: Reg,
+{
+    /// Create a shared handle to the register
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that the register is only ever written,
+    /// through any `Shared<T>`, by writing single bits as `1`. This is meant
+    /// to be called from within [`reg_split!`](crate::reg_split).
+    pub unsafe fn new() -> Self {
+        Shared {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Atomically act on (write a `1` to) a single bit
+    ///
+    /// `bit` is the bit index within the register. For a write-1-to-clear
+    /// latch such as PINT's `rise`/`fall`, this clears that bit's latch.
+    pub fn act(&self, bit: u8) {
+        self.as_atomic().store(1 << bit, Ordering::Release);
+    }
+
+    /// Read whether a single bit is currently set
+    pub fn is_set(&self, bit: u8) -> bool {
+        self.as_atomic().load(Ordering::Acquire) & (1 << bit) != 0
+    }
+
+    fn as_atomic(&self) -> &AtomicU32 {
+        // The register is a plain `u32`-sized MMIO register, and every bit
+        // is independently write-1-to-act, so a non-atomic read composed
+        // with an atomic store of a single bit is race-free between
+        // `Shared` handles; we use an atomic store so the write itself can't
+        // tear.
+        unsafe { &*(T::get() as *const AtomicU32) }
+    }
+}
+
+unsafe impl<T> Sync for Shared<T> where T: Reg {}
+
 #[macro_export]
 macro_rules! reg {
     ($ty:ident, $target:ty, $peripheral:path, $field:ident) => {
@@ -111,6 +232,113 @@ macro_rules! reg {
     };
 }
 
+/// Split a peripheral into uniquely owned registers
+///
+/// Given the concrete raw peripheral type (e.g. `raw::PINT`) and a list of
+/// `field: kind Owner` entries (each `Owner` implementing [`Reg`] via the
+/// [`reg!`] macro, `field` its corresponding register on the peripheral,
+/// and `kind` either `owned` or `shared`), this generates a struct named
+/// `$name` with one field per register, holding a
+/// [`RegOwned`](crate::reg_proxy::RegOwned) or
+/// [`Shared`](crate::reg_proxy::Shared) handle to it respectively, and a
+/// `split` function that takes the *concrete* raw peripheral by value and
+/// produces that struct.
+///
+/// Binding `split` to the concrete raw peripheral type, rather than to an
+/// unconstrained generic, is what actually rules out the aliasing
+/// `RegProxy::new` only warns about: the raw peripheral type is a
+/// non-`Clone` singleton only obtainable once (from `raw::Peripherals::
+/// take()`), so a value of it can be moved into `split` at most once across
+/// the whole program. An unconstrained `fn split<P>(P)` would accept any
+/// dummy value, including one constructible an unbounded number of times,
+/// and so would not have prevented the race at all.
+///
+/// `owned` and `shared` entries are one repetition tagged per-field, so
+/// they can be declared in any order:
+///
+/// ```ignore
+/// reg_split!(
+///     pub struct Pint from raw::PINT {
+///         pmctrl: owned PMCTRL,
+///         rise: shared RISE,
+///         fall: shared FALL,
+///         pmsrc: owned PMSRC,
+///     }
+/// );
+/// ```
+#[macro_export]
+macro_rules! reg_split {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident from $raw:ty {
+            $(
+                $(#[$field_meta:meta])*
+                $field:ident: $kind:ident $owner:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        pub struct $name {
+            $(
+                $(#[$field_meta])*
+                pub $field: $crate::__reg_split_field_ty!($kind $owner),
+            )*
+        }
+
+        impl $name {
+            /// Consume the raw peripheral, producing the uniquely owned
+            /// handles
+            ///
+            /// Taking the peripheral by value, as the concrete `$raw` type
+            /// rather than an unconstrained generic, is what makes the race
+            /// documented on `RegProxy` impossible: `$raw` can only be
+            /// obtained once from `raw::Peripherals::take()`, so this can
+            /// only ever be called with the one real peripheral value, and
+            /// only once.
+            pub fn split(peripheral: $raw) -> Self {
+                // `peripheral` itself is never touched again: every `Reg`
+                // impl reaches the register through the raw peripheral
+                // type's `ptr()`, not through
+                // this value. It only needs to be consumed, so that the
+                // caller can no longer split it a second time.
+                drop(peripheral);
+
+                $name {
+                    $(
+                        $field: $crate::__reg_split_field_init!($kind $owner),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+/// Implementation detail of [`reg_split!`]; resolves a field's declared
+/// `owned`/`shared` kind to its handle type. Not part of the public API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __reg_split_field_ty {
+    (owned $owner:ty) => {
+        $crate::reg_proxy::RegOwned<$owner>
+    };
+    (shared $owner:ty) => {
+        $crate::reg_proxy::Shared<$owner>
+    };
+}
+
+/// Implementation detail of [`reg_split!`]; resolves a field's declared
+/// `owned`/`shared` kind to its constructor. Not part of the public API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __reg_split_field_init {
+    (owned $owner:ty) => {
+        unsafe { $crate::reg_proxy::RegOwned::new() }
+    };
+    (shared $owner:ty) => {
+        unsafe { $crate::reg_proxy::Shared::new() }
+    };
+}
+
 // example:
 // reg!(AHBCLKCTRL0, AHBCLKCTRL0, raw::SYSCON, ahbclkctrl0);
 